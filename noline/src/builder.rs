@@ -7,8 +7,9 @@ use genawaiter::stack::Co;
 use crate::{
     error::Error,
     history::{History, NoHistory, StaticHistory},
+    io_async,
     line_buffer::{Buffer, NoBuffer, StaticBuffer},
-    sync::{self, Read, Write, embedded::IO},
+    sync::{self, FlushPolicy, Read, Write, embedded::IO},
 };
 
 /// Builder for [`sync::Editor`] and [`no_sync::tokio::Editor`].
@@ -34,7 +35,11 @@ use crate::{
 ///     .build_sync(&mut io)
 ///     .unwrap();
 /// ```
-pub struct EditorBuilder<B: Buffer, H: History> {
+/// `N` is the capacity of the buffered-input reader, defaulting to 64 bytes.
+/// Size it with [`with_buffered_input_capacity`](EditorBuilder::with_buffered_input_capacity).
+pub struct EditorBuilder<B: Buffer, H: History, const N: usize = 64> {
+    buffered: bool,
+    flush_policy: FlushPolicy,
     _marker: PhantomData<(B, H)>,
 }
 
@@ -49,28 +54,79 @@ impl EditorBuilder<NoBuffer, NoHistory> {
     /// ```
     pub fn new_static<const N: usize>() -> EditorBuilder<StaticBuffer<N>, NoHistory> {
         EditorBuilder {
+            buffered: false,
+            flush_policy: FlushPolicy::Eager,
             _marker: PhantomData,
         }
     }
 }
 
-impl<B: Buffer, H: History> EditorBuilder<B, H> {
+impl<B: Buffer, H: History, const N: usize> EditorBuilder<B, H, N> {
     /// Add static history
-    pub fn with_static_history<const N: usize>(self) -> EditorBuilder<B, StaticHistory<N>> {
+    pub fn with_static_history<const M: usize>(self) -> EditorBuilder<B, StaticHistory<M>, N> {
         EditorBuilder {
+            buffered: self.buffered,
+            flush_policy: self.flush_policy,
             _marker: PhantomData,
         }
     }
 
+    /// Read input through an escape-sequence aware buffer instead of one
+    /// transport read per byte, keeping the builder's current buffer capacity
+    /// (64 bytes unless set otherwise).
+    ///
+    /// Use [`with_buffered_input_capacity`](Self::with_buffered_input_capacity)
+    /// to size the buffer. See [`sync::embedded::BufReader`] for the buffering
+    /// semantics.
+    pub fn with_buffered_input(mut self) -> Self {
+        self.buffered = true;
+        self
+    }
+
+    /// Read input through an escape-sequence aware buffer of `M` bytes.
+    ///
+    /// Larger buffers absorb more pasted/type-ahead input per fill; see
+    /// [`sync::embedded::BufReader`] for the buffering semantics.
+    pub fn with_buffered_input_capacity<const M: usize>(self) -> EditorBuilder<B, H, M> {
+        EditorBuilder {
+            buffered: true,
+            flush_policy: self.flush_policy,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Defer flushing until a newline or the end of a line render, instead of
+    /// flushing after every write.
+    ///
+    /// See [`sync::FlushPolicy`].
+    pub fn with_line_buffered_output(mut self) -> Self {
+        self.flush_policy = FlushPolicy::LineBuffered;
+        self
+    }
+
     /// Build [`sync::Editor`]. Is equivalent of calling [`sync::Editor::new()`].
     pub async fn build_sync<RW, RE, WE>(
         self,
         co: &mut Co<'_, ()>,
         io: &mut IO<RW>,
-    ) -> Result<sync::Editor<B, H, RW>, Error<RE, WE>>
+    ) -> Result<sync::Editor<B, H, RW, N>, Error<RE, WE>>
     where
         RW: embedded_hal::serial::Read<u8, Error = RE> + embedded_hal::serial::Write<u8, Error = WE>,
     {
-        sync::Editor::new(co, io).await
+        let mut editor = sync::Editor::new(co, io).await?;
+        editor.set_buffered(self.buffered);
+        editor.set_flush_policy(self.flush_policy);
+        Ok(editor)
+    }
+
+    /// Build [`io_async::Editor`]. Is equivalent of calling [`io_async::Editor::new()`].
+    pub async fn build_async<RW>(
+        self,
+        io: &mut io_async::IO<RW>,
+    ) -> Result<io_async::Editor<B, H, RW>, Error<RW::Error, RW::Error>>
+    where
+        RW: embedded_io_async::Read + embedded_io_async::Write,
+    {
+        io_async::Editor::new(io).await
     }
 }
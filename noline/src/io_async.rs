@@ -0,0 +1,188 @@
+//! Line editor for asynchronous IO.
+//!
+//! The editor takes a struct implementing the [`embedded_io_async::Read`] and
+//! [`embedded_io_async::Write`] traits, as exposed by async HAL drivers such as
+//! an Embassy `BufferedUart`. Unlike [`crate::sync`], there is no genawaiter
+//! [`Co`](genawaiter::stack::Co) trampoline: the transport futures are awaited
+//! directly.
+//!
+//! The [`Line`]/[`Output`]/[`Terminal`] state machine is shared with
+//! [`crate::sync`]; only the byte transport differs.
+//!
+//! Use the [`crate::builder::EditorBuilder`] to build an editor.
+use ::core::marker::PhantomData;
+
+use embedded_io_async::{Read, ReadExactError, Write};
+
+use crate::error::Error;
+use crate::history::{get_history_entries, CircularSlice, History};
+use crate::line_buffer::{Buffer, LineBuffer};
+
+use crate::core::{Initializer, InitializerResult, Line};
+use crate::output::{Output, OutputItem};
+use crate::terminal::Terminal;
+
+/// IO wrapper for [`embedded_io_async::Read`] and [`embedded_io_async::Write`]
+pub struct IO<RW>
+where
+    RW: Read + Write,
+{
+    rw: RW,
+}
+
+impl<RW> IO<RW>
+where
+    RW: Read + Write,
+{
+    pub fn new(rw: RW) -> Self {
+        Self { rw }
+    }
+
+    /// Consume self and return wrapped object
+    pub fn take(self) -> RW {
+        self.rw
+    }
+
+    /// Return mutable reference to wrapped object
+    pub fn inner(&mut self) -> &mut RW {
+        &mut self.rw
+    }
+
+    pub async fn read(
+        &mut self,
+    ) -> Result<u8, ReadExactError<<RW as embedded_io_async::ErrorType>::Error>> {
+        let mut buf = [0u8; 1];
+
+        // `read_exact` awaits a full byte and surfaces `Ok(0)` (end-of-stream
+        // per the `embedded_io_async::Read` contract) as `UnexpectedEof`,
+        // rather than busy-spinning on a closed/half-closed transport.
+        self.rw.read_exact(&mut buf).await?;
+
+        Ok(buf[0])
+    }
+
+    pub async fn write(&mut self, buf: &[u8]) -> Result<(), <RW as embedded_io_async::ErrorType>::Error> {
+        self.rw.write_all(buf).await
+    }
+
+    pub async fn flush(&mut self) -> Result<(), <RW as embedded_io_async::ErrorType>::Error> {
+        self.rw.flush().await
+    }
+}
+
+/// Line editor for asynchronous IO
+///
+/// It is recommended to use [`crate::builder::EditorBuilder`] to build an Editor.
+pub struct Editor<B: Buffer, H: History, RW: Read + Write> {
+    buffer: LineBuffer<B>,
+    terminal: Terminal,
+    history: H,
+    _marker: PhantomData<RW>,
+}
+
+impl<B, H, RW> Editor<B, H, RW>
+where
+    B: Buffer,
+    H: History,
+    RW: Read + Write,
+{
+    /// Create and initialize line editor
+    pub async fn new(
+        io: &mut IO<RW>,
+    ) -> Result<Self, Error<<RW as embedded_io_async::ErrorType>::Error, <RW as embedded_io_async::ErrorType>::Error>>
+    {
+        let mut initializer = Initializer::new();
+
+        io.write(Initializer::init())
+            .await
+            .or_else(|err| Error::write_error(err))?;
+        io.flush().await.or_else(|err| Error::write_error(err))?;
+
+        let terminal = loop {
+            let byte = Self::read_byte(io).await?;
+
+            match initializer.advance(byte) {
+                InitializerResult::Continue => (),
+                InitializerResult::Item(terminal) => break terminal,
+                InitializerResult::InvalidInput => return Err(Error::ParserError),
+            }
+        };
+
+        Ok(Self {
+            buffer: LineBuffer::new(),
+            terminal,
+            history: H::default(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Read a byte, mapping an end-of-stream into [`Error::Aborted`] so a closed
+    /// transport ends `readline` instead of the task hanging.
+    async fn read_byte(
+        io: &mut IO<RW>,
+    ) -> Result<u8, Error<<RW as embedded_io_async::ErrorType>::Error, <RW as embedded_io_async::ErrorType>::Error>>
+    {
+        match io.read().await {
+            Ok(byte) => Ok(byte),
+            Err(ReadExactError::UnexpectedEof) => Err(Error::Aborted),
+            Err(ReadExactError::Other(err)) => Error::read_error(err),
+        }
+    }
+
+    async fn handle_output<'b>(
+        output: Output<'b, B>,
+        io: &mut IO<RW>,
+    ) -> Result<Option<()>, Error<<RW as embedded_io_async::ErrorType>::Error, <RW as embedded_io_async::ErrorType>::Error>>
+    {
+        for item in output {
+            if let Some(bytes) = item.get_bytes() {
+                io.write(bytes).await.or_else(|err| Error::write_error(err))?;
+            }
+
+            io.flush().await.or_else(|err| Error::write_error(err))?;
+
+            match item {
+                OutputItem::EndOfString => return Ok(Some(())),
+                OutputItem::Abort => return Err(Error::Aborted),
+                _ => (),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Read line from `io`
+    pub async fn readline<'b>(
+        &'b mut self,
+        prompt: &'b str,
+        io: &mut IO<RW>,
+    ) -> Result<&'b str, Error<<RW as embedded_io_async::ErrorType>::Error, <RW as embedded_io_async::ErrorType>::Error>>
+    {
+        let mut line = Line::new(
+            prompt,
+            &mut self.buffer,
+            &mut self.terminal,
+            &mut self.history,
+        );
+        Self::handle_output(line.reset(), io).await?;
+
+        loop {
+            let byte = Self::read_byte(io).await?;
+            if Self::handle_output(line.advance(byte), io).await?.is_some() {
+                break;
+            }
+        }
+
+        Ok(self.buffer.as_str())
+    }
+
+    /// Load history from iterator
+    pub fn load_history<'a>(&mut self, entries: impl Iterator<Item = &'a str>) -> usize {
+        self.history.load_entries(entries)
+    }
+
+    /// Get history as iterator over circular slices
+    pub fn get_history<'a>(&'a self) -> impl Iterator<Item = CircularSlice<'a>> {
+        get_history_entries(&self.history)
+    }
+}
@@ -0,0 +1,209 @@
+//! IO backed by a shared byte-pipe.
+//!
+//! Decouples byte arrival from the editor's control flow. One task — a USB-CDC
+//! or network RX handler — pushes raw bytes into the pipe's [`Writer`], while
+//! the [`crate::io_async::Editor`] reads from the [`Reader`] end through [`IO`].
+//! Outgoing bytes go out a separate [`embedded_io_async::Write`] sink, so the
+//! editor never needs to own the UART directly.
+use core::cell::RefCell;
+use core::future::poll_fn;
+use core::task::{Poll, Waker};
+
+use critical_section::Mutex;
+use embedded_io_async::{ErrorType, Read, Write};
+
+struct State<const N: usize> {
+    buf: [u8; N],
+    head: usize,
+    tail: usize,
+    len: usize,
+    reader_waker: Option<Waker>,
+}
+
+impl<const N: usize> State<N> {
+    const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            head: 0,
+            tail: 0,
+            len: 0,
+            reader_waker: None,
+        }
+    }
+
+    fn push(&mut self, byte: u8) -> bool {
+        if self.len == N {
+            return false;
+        }
+
+        self.buf[self.tail] = byte;
+        self.tail = (self.tail + 1) % N;
+        self.len += 1;
+        true
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let byte = self.buf[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(byte)
+    }
+}
+
+/// Single-producer ring-buffer byte pipe with an `N` byte backing buffer.
+///
+/// State is guarded by a [`critical_section::Mutex`], so the [`Writer`] end can
+/// be driven from an interrupt or a separate-priority executor while the
+/// [`Reader`] task awaits on the other end.
+pub struct Pipe<const N: usize> {
+    state: Mutex<RefCell<State<N>>>,
+}
+
+impl<const N: usize> Default for Pipe<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Pipe<N> {
+    /// Create an empty pipe
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(RefCell::new(State::new())),
+        }
+    }
+
+    /// Split the pipe into its [`Reader`] and [`Writer`] ends
+    pub fn split(&self) -> (Reader<'_, N>, Writer<'_, N>) {
+        (Reader { pipe: self }, Writer { pipe: self })
+    }
+}
+
+/// Reading end of a [`Pipe`]
+pub struct Reader<'p, const N: usize> {
+    pipe: &'p Pipe<N>,
+}
+
+impl<'p, const N: usize> Reader<'p, N> {
+    /// Fill `buf` with as many bytes as are available, suspending until at
+    /// least one byte has been pushed by the [`Writer`].
+    pub async fn read(&mut self, buf: &mut [u8]) -> usize {
+        poll_fn(|cx| {
+            critical_section::with(|cs| {
+                let mut state = self.pipe.state.borrow_ref_mut(cs);
+
+                if state.len == 0 {
+                    match &mut state.reader_waker {
+                        Some(waker) => waker.clone_from(cx.waker()),
+                        slot => *slot = Some(cx.waker().clone()),
+                    }
+                    return Poll::Pending;
+                }
+
+                let mut read = 0;
+                while read < buf.len() {
+                    match state.pop() {
+                        Some(byte) => {
+                            buf[read] = byte;
+                            read += 1;
+                        }
+                        None => break,
+                    }
+                }
+
+                Poll::Ready(read)
+            })
+        })
+        .await
+    }
+}
+
+/// Writing end of a [`Pipe`]
+pub struct Writer<'p, const N: usize> {
+    pipe: &'p Pipe<N>,
+}
+
+impl<'p, const N: usize> Writer<'p, N> {
+    /// Push as many bytes from `buf` as fit, waking a blocked reader.
+    ///
+    /// Returns the number of bytes accepted; excess bytes are dropped when the
+    /// buffer is full.
+    pub fn write(&mut self, buf: &[u8]) -> usize {
+        let (written, waker) = critical_section::with(|cs| {
+            let mut state = self.pipe.state.borrow_ref_mut(cs);
+
+            let mut written = 0;
+            while written < buf.len() && state.push(buf[written]) {
+                written += 1;
+            }
+
+            let waker = if written > 0 {
+                state.reader_waker.take()
+            } else {
+                None
+            };
+
+            (written, waker)
+        });
+
+        // Wake outside the critical section to keep it short.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        written
+    }
+}
+
+/// IO over the [`Reader`] end of a [`Pipe`], writing out a separate sink.
+pub struct IO<'p, const N: usize, W> {
+    reader: Reader<'p, N>,
+    writer: W,
+}
+
+impl<'p, const N: usize, W> IO<'p, N, W>
+where
+    W: Write,
+{
+    pub fn new(reader: Reader<'p, N>, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Consume self and return the wrapped write sink
+    pub fn take(self) -> W {
+        self.writer
+    }
+}
+
+impl<'p, const N: usize, W> ErrorType for IO<'p, N, W>
+where
+    W: Write,
+{
+    type Error = W::Error;
+}
+
+impl<'p, const N: usize, W> Read for IO<'p, N, W>
+where
+    W: Write,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.reader.read(buf).await)
+    }
+}
+
+impl<'p, const N: usize, W> Write for IO<'p, N, W>
+where
+    W: Write,
+{
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.writer.write(buf).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.writer.flush().await
+    }
+}
@@ -17,6 +17,23 @@ use crate::core::{Initializer, InitializerResult, Line};
 use crate::output::{Output, OutputItem};
 use crate::terminal::Terminal;
 
+/// Policy selecting when buffered output is flushed to the transport.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlushPolicy {
+    /// Flush after every [`OutputItem`] (the default)
+    Eager,
+    /// Write output eagerly but defer the [`flush`](Write::flush) until a
+    /// newline crosses the wire or the line render ends, like
+    /// [`std::io::LineWriter`] — cutting flush calls on slow transports
+    LineBuffered,
+}
+
+impl Default for FlushPolicy {
+    fn default() -> Self {
+        Self::Eager
+    }
+}
+
 /// Trait for reading bytes from input
 pub trait Read {
     type Error;
@@ -36,17 +53,86 @@ pub trait Write {
     fn flush(&mut self) -> Result<(), Self::Error>;
 }
 
+/// Bridge adapting a blocking [`Read`]/[`Write`] pair to the
+/// [`embedded_hal::serial`] traits consumed by [`embedded::IO`].
+///
+/// A blocking transport never reports "would block", so the `nb` reads and
+/// writes always complete immediately. This lets any [`Read`] + [`Write`]
+/// implementation — e.g. [`std::IO`] or [`cursor::IO`] — drive an [`Editor`]
+/// through [`embedded::IO`]:
+///
+/// ```no_run
+/// # async fn f(co: &mut genawaiter::stack::Co<'_, ()>) {
+/// # let stream: std::net::TcpStream = unimplemented!();
+/// use noline::builder::EditorBuilder;
+/// use noline::sync::{Blocking, std::IO, embedded};
+///
+/// let mut io = embedded::IO::new(Blocking::new(IO::new(stream)));
+/// let mut editor = EditorBuilder::new_static::<100>()
+///     .build_sync(co, &mut io)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub struct Blocking<T> {
+    inner: T,
+}
+
+impl<T> Blocking<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Consume self and return wrapped object
+    pub fn take(self) -> T {
+        self.inner
+    }
+
+    /// Return mutable reference to wrapped object
+    pub fn inner(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: Read> serial::Read<u8> for Blocking<T> {
+    type Error = <T as Read>::Error;
+
+    fn read(&mut self) -> ::nb::Result<u8, Self::Error> {
+        self.inner.read().map_err(::nb::Error::Other)
+    }
+}
+
+impl<T: Write> serial::Write<u8> for Blocking<T> {
+    type Error = <T as Write>::Error;
+
+    fn write(&mut self, word: u8) -> ::nb::Result<(), Self::Error> {
+        self.inner.write(&[word]).map_err(::nb::Error::Other)
+    }
+
+    fn flush(&mut self) -> ::nb::Result<(), Self::Error> {
+        Write::flush(&mut self.inner).map_err(::nb::Error::Other)
+    }
+}
+
 /// Line editor for synchronous IO
 ///
 /// It is recommended to use [`crate::builder::EditorBuilder`] to build an Editor.
-pub struct Editor<B: Buffer, H: History, RW: serial::Read<u8> + serial::Write<u8>> {
+///
+/// `N` is the capacity of the buffered-input reader (see
+/// [`crate::builder::EditorBuilder::with_buffered_input`]); it is unused unless
+/// the buffered path is enabled.
+pub struct Editor<B: Buffer, H: History, RW: serial::Read<u8> + serial::Write<u8>, const N: usize = 64>
+{
     buffer: LineBuffer<B>,
     terminal: Terminal,
     history: H,
+    buffered: bool,
+    flush_policy: FlushPolicy,
+    reader: embedded::BufReader<N>,
     _marker: PhantomData<RW>,
 }
 
-impl<B, H, RW, RE, WE> Editor<B, H, RW>
+impl<B, H, RW, RE, WE, const N: usize> Editor<B, H, RW, N>
 where
     B: Buffer,
     H: History,
@@ -78,27 +164,57 @@ where
             buffer: LineBuffer::new(),
             terminal,
             history: H::default(),
+            buffered: false,
+            flush_policy: FlushPolicy::default(),
+            reader: embedded::BufReader::new(),
             _marker: PhantomData,
         })
     }
 
+    /// Opt into the escape-sequence aware buffered input path.
+    ///
+    /// Called by [`crate::builder::EditorBuilder::with_buffered_input`].
+    pub(crate) fn set_buffered(&mut self, buffered: bool) {
+        self.buffered = buffered;
+    }
+
+    /// Select when output is flushed to the transport.
+    ///
+    /// Called by [`crate::builder::EditorBuilder::with_line_buffered_output`].
+    pub(crate) fn set_flush_policy(&mut self, policy: FlushPolicy) {
+        self.flush_policy = policy;
+    }
+
     async fn handle_output<'b>(
         co: &mut Co<'_, ()>,
         output: Output<'b, B>,
         io: &mut embedded::IO<RW>,
+        policy: FlushPolicy,
     ) -> Result<Option<()>, Error<RE, WE>> {
         for item in output {
             if let Some(bytes) = item.get_bytes() {
                 io.write(co, bytes)
                     .await
                     .or_else(|err| Error::write_error(err))?;
-            }
 
-            io.flush(co).await.or_else(|err| Error::write_error(err))?;
+                // In line-buffered mode defer the flush until a newline crosses
+                // the wire, cutting flush calls on slow transports.
+                if policy == FlushPolicy::Eager || bytes.contains(&b'\n') {
+                    io.flush(co).await.or_else(|err| Error::write_error(err))?;
+                }
+            }
 
             match item {
-                OutputItem::EndOfString => return Ok(Some(())),
-                OutputItem::Abort => return Err(Error::Aborted),
+                OutputItem::EndOfString => {
+                    // Guaranteed flush on readline return.
+                    io.flush(co).await.or_else(|err| Error::write_error(err))?;
+                    return Ok(Some(()));
+                }
+                OutputItem::Abort => {
+                    // Guaranteed flush on abort.
+                    io.flush(co).await.or_else(|err| Error::write_error(err))?;
+                    return Err(Error::Aborted);
+                }
                 _ => (),
             }
         }
@@ -113,21 +229,63 @@ where
         prompt: &'b str,
         io: &mut embedded::IO<RW>,
     ) -> Result<&'b str, Error<RE, WE>> {
+        let policy = self.flush_policy;
+        let buffered = self.buffered;
         let mut line = Line::new(
             prompt,
             &mut self.buffer,
             &mut self.terminal,
             &mut self.history,
         );
-        Self::handle_output(co, line.reset(), io).await?;
+        Self::handle_output(co, line.reset(), io, policy).await?;
+
+        if buffered {
+            // Drain every byte the transport hands us into `line.advance` before
+            // awaiting a new fill. Partial escape sequences are never misparsed:
+            // the parser keeps its state across `advance` calls and we only
+            // `consume` bytes after they have been fed to it. The reader lives on
+            // the `Editor`, so any type-ahead buffered past the line terminator
+            // survives into the next `readline` call instead of being dropped.
+            'fill: loop {
+                let available = self
+                    .reader
+                    .fill_buf(co, io)
+                    .await
+                    .or_else(|err| Error::read_error(err))?
+                    .len();
+                let mut consumed = 0;
+
+                while consumed < available {
+                    let byte = self.reader.buffer()[consumed];
+                    consumed += 1;
+
+                    // Consume the handled bytes before propagating `EndOfString`
+                    // or an error (e.g. `Abort`), so the terminator and any
+                    // already-fed type-ahead are not replayed into the next line.
+                    match Self::handle_output(co, line.advance(byte), io, policy).await {
+                        Ok(Some(())) => {
+                            self.reader.consume(consumed);
+                            break 'fill;
+                        }
+                        Ok(None) => (),
+                        Err(err) => {
+                            self.reader.consume(consumed);
+                            return Err(err);
+                        }
+                    }
+                }
 
-        loop {
-            let byte = io.read(co).await.or_else(|err| Error::read_error(err))?;
-            if Self::handle_output(co, line.advance(byte), io)
-                .await?
-                .is_some()
-            {
-                break;
+                self.reader.consume(consumed);
+            }
+        } else {
+            loop {
+                let byte = io.read(co).await.or_else(|err| Error::read_error(err))?;
+                if Self::handle_output(co, line.advance(byte), io, policy)
+                    .await?
+                    .is_some()
+                {
+                    break;
+                }
             }
         }
 
@@ -219,12 +377,270 @@ pub mod embedded {
         }
     }
 
-    // impl<RW> fmt::Write for IO<RW>
-    // where
-    //     RW: serial::Read<u8> + serial::Write<u8>,
-    // {
-    //     fn write_str(&mut self, s: &str) -> fmt::Result {
-    //         self.write(s.as_bytes()).or(Err(fmt::Error))
-    //     }
-    // }
+    /// Escape-sequence aware buffered reader over an [`IO`].
+    ///
+    /// Mirrors [`std::io::BufReader`]: [`fill_buf`](Self::fill_buf) exposes the
+    /// bytes currently buffered and [`consume`](Self::consume) advances past the
+    /// bytes that have been handed to the parser. The capacity is the const
+    /// parameter `N`, following the crate's static-buffer convention.
+    pub struct BufReader<const N: usize> {
+        buf: [u8; N],
+        pos: usize,
+        len: usize,
+    }
+
+    impl<const N: usize> Default for BufReader<N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<const N: usize> BufReader<N> {
+        /// Create a buffered reader with a `N` byte buffer
+        pub fn new() -> Self {
+            Self {
+                buf: [0; N],
+                pos: 0,
+                len: 0,
+            }
+        }
+
+        /// Return a reference to the unconsumed buffered bytes, refilling from
+        /// the transport when empty.
+        ///
+        /// Refilling reads as many bytes as the transport will yield without
+        /// blocking; if nothing is available yet the reader yields through `co`
+        /// and retries, so at least one byte is buffered on return. Unconsumed
+        /// bytes from a previous fill — e.g. the head of a split escape
+        /// sequence — are preserved until [`consume`](Self::consume)d.
+        pub async fn fill_buf<RW, RE, WE>(
+            &mut self,
+            co: &mut Co<'_, ()>,
+            io: &mut IO<RW>,
+        ) -> Result<&[u8], RE>
+        where
+            RW: serial::Read<u8, Error = RE> + serial::Write<u8, Error = WE>,
+        {
+            if self.pos == self.len {
+                self.pos = 0;
+                self.len = 0;
+
+                loop {
+                    while self.len < N {
+                        match io.inner().read() {
+                            Ok(byte) => {
+                                self.buf[self.len] = byte;
+                                self.len += 1;
+                            }
+                            Err(::nb::Error::WouldBlock) => break,
+                            Err(::nb::Error::Other(err)) => return Err(err),
+                        }
+                    }
+
+                    if self.len > 0 {
+                        break;
+                    }
+
+                    co.yield_(()).await;
+                }
+            }
+
+            Ok(&self.buf[self.pos..self.len])
+        }
+
+        /// Mark `n` buffered bytes as handed to the parser.
+        ///
+        /// Only bytes returned by [`fill_buf`](Self::fill_buf) may be consumed;
+        /// `n` is clamped to the number of buffered bytes. Bytes consumed here
+        /// are dropped whether or not they were fed to the parser.
+        pub fn consume(&mut self, n: usize) {
+            self.pos = (self.pos + n).min(self.len);
+        }
+
+        /// Return the currently buffered, unconsumed bytes without refilling
+        pub fn buffer(&self) -> &[u8] {
+            &self.buf[self.pos..self.len]
+        }
+    }
+
+    /// Formatted output between `readline` calls.
+    ///
+    /// `write_str` blocks on the underlying [`serial::Write`] — it does not use
+    /// the `co` yield trampoline — so it is meant for writing prompts or status
+    /// outside the editor's async loop, e.g. `write!(io, "{status}")`.
+    impl<RW> fmt::Write for IO<RW>
+    where
+        RW: serial::Read<u8> + serial::Write<u8>,
+    {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for &b in s.as_bytes() {
+                ::nb::block!(self.rw.write(b)).or(Err(fmt::Error))?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub mod std {
+    //! IO implementation using [`std::io::Read`] and [`std::io::Write`]. Requires feature `std`.
+    //!
+    //! Any standard stream — a TCP socket, PTY or pipe — can host the editor.
+    //! Wrap it in [`super::Blocking`] to drive an [`super::Editor`] through
+    //! [`super::embedded::IO`]. Errors are the underlying [`std::io::Error`]
+    //! and are not mapped to a dedicated variant: the editor reports a failed
+    //! read through `Error::read_error` and a failed write through
+    //! `Error::write_error`.
+    use ::std::io;
+
+    use super::{Read, Write};
+
+    /// IO wrapper for [`std::io::Read`] and [`std::io::Write`]
+    pub struct IO<RW>
+    where
+        RW: io::Read + io::Write,
+    {
+        rw: RW,
+    }
+
+    impl<RW> IO<RW>
+    where
+        RW: io::Read + io::Write,
+    {
+        pub fn new(rw: RW) -> Self {
+            Self { rw }
+        }
+
+        /// Consume self and return wrapped object
+        pub fn take(self) -> RW {
+            self.rw
+        }
+
+        /// Return mutable reference to wrapped object
+        pub fn inner(&mut self) -> &mut RW {
+            &mut self.rw
+        }
+    }
+
+    impl<RW> Read for IO<RW>
+    where
+        RW: io::Read + io::Write,
+    {
+        type Error = io::Error;
+
+        fn read(&mut self) -> Result<u8, Self::Error> {
+            let mut buf = [0u8; 1];
+
+            match io::Read::read(&mut self.rw, &mut buf)? {
+                0 => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                _ => Ok(buf[0]),
+            }
+        }
+    }
+
+    impl<RW> Write for IO<RW>
+    where
+        RW: io::Read + io::Write,
+    {
+        type Error = io::Error;
+
+        fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            io::Write::write_all(&mut self.rw, buf)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            io::Write::flush(&mut self.rw)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub mod cursor {
+    //! In-memory [`Read`]/[`Write`] for deterministic editor testing. Requires feature `std`.
+    //!
+    //! Drive an editor from a fixed input script — the simulated keystrokes,
+    //! including escape sequences for arrows and history — and inspect the exact
+    //! bytes it wrote afterwards, without hardware or a PTY. Wrap it in
+    //! [`super::Blocking`] to feed an [`super::Editor`] through
+    //! [`super::embedded::IO`].
+    //!
+    //! ```no_run
+    //! # async fn f(co: &mut genawaiter::stack::Co<'_, ()>) {
+    //! use noline::builder::EditorBuilder;
+    //! use noline::sync::{Blocking, cursor::IO, embedded};
+    //!
+    //! // On init the editor sends the DSR query `\x1b[6n`; the terminal replies
+    //! // with a CPR report — here `\x1b[1;1R`, the response scripted below.
+    //! let mut io = embedded::IO::new(Blocking::new(IO::new(b"\x1b[1;1R\"foo\r")));
+    //! let mut editor = EditorBuilder::new_static::<100>()
+    //!     .build_sync(co, &mut io)
+    //!     .await
+    //!     .unwrap();
+    //!
+    //! let line = editor.readline(co, "> ", &mut io).await.unwrap();
+    //! assert_eq!(line, "\"foo");
+    //!
+    //! // The written bytes can be asserted against the expected terminal output.
+    //! let _output = io.take().take().into_output();
+    //! # }
+    //! ```
+    use core::convert::Infallible;
+
+    use super::{Read, Write};
+
+    /// Error signalling the input cursor has reached the end of the script
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct EndOfInput;
+
+    /// In-memory IO driven by a fixed input byte slice
+    pub struct IO<'a> {
+        input: &'a [u8],
+        position: usize,
+        output: Vec<u8>,
+    }
+
+    impl<'a> IO<'a> {
+        /// Create IO reading from `input`
+        pub fn new(input: &'a [u8]) -> Self {
+            Self {
+                input,
+                position: 0,
+                output: Vec::new(),
+            }
+        }
+
+        /// Return the bytes written by the editor so far
+        pub fn output(&self) -> &[u8] {
+            &self.output
+        }
+
+        /// Consume self and return the collected output
+        pub fn into_output(self) -> Vec<u8> {
+            self.output
+        }
+    }
+
+    impl Read for IO<'_> {
+        type Error = EndOfInput;
+
+        fn read(&mut self) -> Result<u8, Self::Error> {
+            let byte = *self.input.get(self.position).ok_or(EndOfInput)?;
+            self.position += 1;
+            Ok(byte)
+        }
+    }
+
+    impl Write for IO<'_> {
+        type Error = Infallible;
+
+        fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            self.output.extend_from_slice(buf);
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
 }